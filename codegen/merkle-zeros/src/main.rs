@@ -6,6 +6,7 @@ use anyhow::{anyhow, Result};
 use clap::Parser;
 use renegade_constants::{Scalar, MERKLE_HEIGHT};
 use renegade_crypto::hash::compute_poseidon_hash;
+use renegade_crypto::hash::Poseidon2Params;
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
@@ -13,6 +14,8 @@ use tiny_keccak::{Hasher, Keccak};
 
 /// Name of the Solidity contract to generate
 const CONTRACT_NAME: &str = "MerkleZeros";
+/// Name of the generated Poseidon gadget library
+const POSEIDON_CONTRACT_NAME: &str = "Poseidon2";
 /// The string that is used to create leaf zero values
 const LEAF_KECCAK_PREIMAGE: &str = "renegade";
 
@@ -21,17 +24,31 @@ const LEAF_KECCAK_PREIMAGE: &str = "renegade";
 #[clap(author, version, about)]
 struct Args {
     /// Path to output directory for the generated Solidity file
-    #[clap(short, long, default_value = "./")]
+    #[clap(short, long, default_value = "./generated")]
     output_dir: PathBuf,
+    /// Also emit a self-contained `Poseidon2` Solidity library so contracts can
+    /// recompute roots with the exact permutation used by `renegade_crypto`
+    #[clap(long)]
+    emit_poseidon: bool,
 }
 
 /// Generate the Solidity contract with Merkle tree zero values
-fn generate_solidity_contract() -> Result<String> {
+///
+/// When `emit_poseidon` is set, the incremental Merkle tree helpers (which
+/// compress with Poseidon) and the `Poseidon2` import are included; otherwise
+/// only the zero-value constants and the `getZeroValue` getter are emitted so
+/// the file is self-contained.
+fn generate_solidity_contract(emit_poseidon: bool) -> Result<String> {
     // Contract header
     let mut contract = String::new();
     contract.push_str("// SPDX-License-Identifier: MIT\n");
     contract.push_str("pragma solidity ^0.8.0;\n\n");
     contract.push_str("// ⚠ ️WARNING: This file is auto-generated by `codegen/merkle-zeros`. Do not edit directly.\n");
+    // The incremental tree helpers below compress with Poseidon; pull in the
+    // companion library emitted alongside it so roots match `renegade_crypto`.
+    if emit_poseidon {
+        contract.push_str("import {Poseidon2} from \"./Poseidon2.sol\";\n\n");
+    }
     contract.push_str(&format!("library {} {{\n", CONTRACT_NAME));
 
     // Add a comment to indicate the preimage
@@ -80,11 +97,136 @@ fn generate_solidity_contract() -> Result<String> {
     contract.push_str("\t\t}\n");
     contract.push_str("\t}\n");
 
+    // Append the append-only incremental Merkle tree helpers that reuse the
+    // zero values generated above. These depend on the Poseidon2 companion, so
+    // they are only emitted when that companion is generated too.
+    if emit_poseidon {
+        contract.push_str(&generate_incremental_tree());
+    }
+
     // Close contract
     contract.push_str("}\n");
     Ok(contract)
 }
 
+/// Generate the append-only incremental Merkle tree helpers
+///
+/// The emitted code keeps a frontier of `MERKLE_HEIGHT` filled subtrees plus a
+/// running leaf index rather than the full tree. Each insert walks from the
+/// leaf level up to the root, touching one storage slot and performing one
+/// Poseidon hash per level, so an append costs `O(MERKLE_HEIGHT)` hashes.
+fn generate_incremental_tree() -> String {
+    let mut s = String::new();
+
+    // Tree state struct. The filled subtrees are initialized to the zero values
+    // above by `initTree`, so a freshly constructed tree hashes to ZERO_VALUE_ROOT.
+    s.push_str("\n\t/// @notice State for an append-only incremental Merkle tree\n");
+    s.push_str("\t/// @dev `filledSubtrees[i]` holds the left-child hash at height `i` that is\n");
+    s.push_str("\t/// waiting for a right sibling; `nextLeafIndex` is the index of the next\n");
+    s.push_str("\t/// leaf to be appended.\n");
+    s.push_str("\tstruct IncrementalMerkleTree {\n");
+    s.push_str("\t\tuint256 nextLeafIndex;\n");
+    s.push_str("\t\tuint256 root;\n");
+    s.push_str(&format!(
+        "\t\tuint256[{}] filledSubtrees;\n",
+        MERKLE_HEIGHT
+    ));
+    s.push_str("\t}\n");
+
+    // Two-to-one Poseidon compression stub. This MUST match the permutation used
+    // by `renegade_crypto::hash::compute_poseidon_hash` off-chain, otherwise the
+    // zero values above and the roots computed here will disagree. Use
+    // `--emit-poseidon` to generate the matching `Poseidon2` library.
+    s.push_str("\n\t/// @notice Two-to-one Poseidon compression, matching `renegade_crypto`\n");
+    s.push_str("\t/// @param left The left input\n");
+    s.push_str("\t/// @param right The right input\n");
+    s.push_str("\t/// @return The Poseidon hash of the two inputs\n");
+    s.push_str("\tfunction hash2(uint256 left, uint256 right) internal view returns (uint256) {\n");
+    s.push_str("\t\treturn Poseidon2.hash2(left, right);\n");
+    s.push_str("\t}\n");
+
+    // Initialize the frontier to the precomputed zero values.
+    s.push_str("\n\t/// @notice Initialize an empty incremental Merkle tree\n");
+    s.push_str("\t/// @param tree The tree to initialize\n");
+    s.push_str("\tfunction initTree(IncrementalMerkleTree storage tree) internal {\n");
+    // `filledSubtrees[i]` lives at level `i` counting up from the leaves, but the
+    // constants are emitted root-first (ZERO_VALUE_0 is the level just below the
+    // root), so the leaf-relative level `i` maps to ZERO_VALUE_{MERKLE_HEIGHT-1-i}.
+    for i in 0..MERKLE_HEIGHT {
+        s.push_str(&format!(
+            "\t\ttree.filledSubtrees[{}] = ZERO_VALUE_{};\n",
+            i,
+            MERKLE_HEIGHT - 1 - i
+        ));
+    }
+    s.push_str("\t\ttree.root = ZERO_VALUE_ROOT;\n");
+    s.push_str("\t}\n");
+
+    // Append-only insertion following the frontier algorithm.
+    s.push_str("\n\t/// @notice Append a leaf to the tree and return the new root\n");
+    s.push_str("\t/// @param tree The tree to insert into\n");
+    s.push_str("\t/// @param leaf The leaf value to append\n");
+    s.push_str("\t/// @return The root after inserting `leaf`\n");
+    s.push_str(
+        "\tfunction insert(IncrementalMerkleTree storage tree, uint256 leaf) internal returns (uint256) {\n",
+    );
+    s.push_str(&format!(
+        "\t\trequire(tree.nextLeafIndex < (1 << {}), \"MerkleZeros: tree is full\");\n",
+        MERKLE_HEIGHT
+    ));
+    s.push_str("\t\tuint256 index = tree.nextLeafIndex;\n");
+    s.push_str("\t\tuint256 cur = leaf;\n");
+    s.push_str(&format!(
+        "\t\tfor (uint256 i = 0; i < {}; i++) {{\n",
+        MERKLE_HEIGHT
+    ));
+    s.push_str("\t\t\tif ((index >> i) & 1 == 0) {\n");
+    s.push_str("\t\t\t\t// Left child: record this node as the new filled subtree and\n");
+    s.push_str("\t\t\t\t// hash against the empty right subtree at this height. The zero\n");
+    s.push_str("\t\t\t\t// values are root-first, so level `i` maps to getZeroValue(H-1-i).\n");
+    s.push_str("\t\t\t\ttree.filledSubtrees[i] = cur;\n");
+    s.push_str(&format!(
+        "\t\t\t\tcur = hash2(cur, getZeroValue({} - i));\n",
+        MERKLE_HEIGHT - 1
+    ));
+    s.push_str("\t\t\t} else {\n");
+    s.push_str("\t\t\t\t// Right child: hash against the previously filled left subtree.\n");
+    s.push_str("\t\t\t\tcur = hash2(tree.filledSubtrees[i], cur);\n");
+    s.push_str("\t\t\t}\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\ttree.root = cur;\n");
+    s.push_str("\t\ttree.nextLeafIndex = index + 1;\n");
+    s.push_str("\t\treturn cur;\n");
+    s.push_str("\t}\n");
+
+    // Membership helper: recompute a root from a leaf and its authentication path.
+    s.push_str("\n\t/// @notice Recompute a Merkle root from a leaf and its authentication path\n");
+    s.push_str("\t/// @param leaf The leaf value\n");
+    s.push_str("\t/// @param index The leaf index, whose bits select left/right at each height\n");
+    s.push_str("\t/// @param pathElements The sibling hashes from leaf to root\n");
+    s.push_str("\t/// @return The recomputed root\n");
+    s.push_str(
+        "\tfunction computeRoot(uint256 leaf, uint256 index, uint256[] memory pathElements)\n",
+    );
+    s.push_str("\t\tinternal\n\t\tview\n\t\treturns (uint256)\n\t{\n");
+    s.push_str(&format!(
+        "\t\trequire(pathElements.length == {}, \"MerkleZeros: bad path length\");\n",
+        MERKLE_HEIGHT
+    ));
+    s.push_str("\t\tuint256 cur = leaf;\n");
+    s.push_str("\t\tfor (uint256 i = 0; i < pathElements.length; i++) {\n");
+    s.push_str("\t\t\tif ((index >> i) & 1 == 0) {\n");
+    s.push_str("\t\t\t\tcur = hash2(cur, pathElements[i]);\n");
+    s.push_str("\t\t\t} else {\n");
+    s.push_str("\t\t\t\tcur = hash2(pathElements[i], cur);\n");
+    s.push_str("\t\t\t}\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\treturn cur;\n");
+    s.push_str("\t}\n");
+
+    s
+}
+
 /// Generate the zero values for each height in the Merkle tree
 fn generate_zero_values() -> Vec<Scalar> {
     let mut result = vec![generate_leaf_zero_value()];
@@ -112,6 +254,322 @@ fn generate_leaf_zero_value() -> Scalar {
     Scalar::from_be_bytes_mod_order(&output)
 }
 
+/// The width (state size `t`) of the Poseidon2 permutation used for the
+/// two-to-one compression in `renegade_crypto`
+const POSEIDON_WIDTH: usize = 3;
+/// The BN254 scalar field modulus, the field Poseidon2 operates over
+const BN254_SCALAR_MODULUS: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+/// Number of test vectors to embed so CI catches any Rust/Solidity divergence
+const POSEIDON_TEST_VECTORS: usize = 4;
+
+/// Format an iterator of scalars as a Solidity array literal body
+fn format_scalar_array<'a, I: IntoIterator<Item = &'a Scalar>>(values: I) -> String {
+    values
+        .into_iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Generate a self-contained `Poseidon2` Solidity library
+///
+/// The round constants and MDS/internal matrices are emitted directly from the
+/// parameters `renegade_crypto` uses, so the on-chain permutation stays in
+/// lockstep with `compute_poseidon_hash`. The embedded test vectors are
+/// computed in Rust and asserted in Solidity, so CI fails loudly if the two
+/// implementations ever diverge.
+fn generate_poseidon_contract() -> String {
+    let params = Poseidon2Params::default();
+    let external_rc = params.external_round_constants();
+    let internal_rc = params.internal_round_constants();
+    let external_mds = params.external_mds();
+    let internal_mat = params.internal_matrix();
+
+    let mut s = String::new();
+    s.push_str("// SPDX-License-Identifier: MIT\n");
+    s.push_str("pragma solidity ^0.8.0;\n\n");
+    s.push_str("// ⚠ ️WARNING: This file is auto-generated by `codegen/merkle-zeros`. Do not edit directly.\n");
+    s.push_str(&format!("library {} {{\n", POSEIDON_CONTRACT_NAME));
+
+    // Field modulus and round schedule
+    s.push_str(&format!(
+        "\tuint256 constant public PRIME = {};\n",
+        BN254_SCALAR_MODULUS
+    ));
+    s.push_str(&format!(
+        "\tuint256 constant FULL_ROUNDS = {};\n",
+        params.full_rounds()
+    ));
+    s.push_str(&format!(
+        "\tuint256 constant PARTIAL_ROUNDS = {};\n\n",
+        params.partial_rounds()
+    ));
+
+    // External (full-round) round constants, one row of `WIDTH` per round
+    s.push_str("\t/// @dev Round constants for the external (full) rounds\n");
+    s.push_str(
+        "\tfunction externalRoundConstant(uint256 round, uint256 lane) internal pure returns (uint256) {\n",
+    );
+    s.push_str(&format!(
+        "\t\tuint256[{}][{}] memory rc = [\n",
+        POSEIDON_WIDTH,
+        external_rc.len()
+    ));
+    for row in external_rc.iter() {
+        s.push_str(&format!("\t\t\t[{}],\n", format_scalar_array(row.iter())));
+    }
+    s.push_str("\t\t];\n");
+    s.push_str("\t\treturn rc[round][lane];\n");
+    s.push_str("\t}\n\n");
+
+    // Internal (partial-round) round constants, one scalar per round added to lane 0
+    s.push_str("\t/// @dev Round constants for the internal (partial) rounds\n");
+    s.push_str("\tfunction internalRoundConstant(uint256 round) internal pure returns (uint256) {\n");
+    s.push_str(&format!(
+        "\t\tuint256[{}] memory rc = [{}];\n",
+        internal_rc.len(),
+        format_scalar_array(internal_rc.iter())
+    ));
+    s.push_str("\t\treturn rc[round];\n");
+    s.push_str("\t}\n\n");
+
+    // MDS matrices used by the external and internal linear layers
+    s.push_str(&generate_matrix_getter("externalMds", &external_mds));
+    s.push_str(&generate_matrix_getter("internalMatrix", &internal_mat));
+
+    // The S-box: x^5 mod p
+    s.push_str("\t/// @dev The Poseidon2 S-box, x^5 in the scalar field\n");
+    s.push_str("\tfunction sbox(uint256 x) internal pure returns (uint256) {\n");
+    s.push_str("\t\tuint256 x2 = mulmod(x, x, PRIME);\n");
+    s.push_str("\t\tuint256 x4 = mulmod(x2, x2, PRIME);\n");
+    s.push_str("\t\treturn mulmod(x4, x, PRIME);\n");
+    s.push_str("\t}\n\n");
+
+    // Dense matrix-vector multiply over the field
+    s.push_str("\t/// @dev Multiply the state by a dense `WIDTH x WIDTH` matrix in place\n");
+    s.push_str(&format!(
+        "\tfunction applyMatrix(uint256[{w}] memory state, bool internalLayer) internal pure {{\n",
+        w = POSEIDON_WIDTH
+    ));
+    s.push_str(&format!("\t\tuint256[{w}] memory next;\n", w = POSEIDON_WIDTH));
+    s.push_str(&format!("\t\tfor (uint256 i = 0; i < {}; i++) {{\n", POSEIDON_WIDTH));
+    s.push_str("\t\t\tuint256 acc = 0;\n");
+    s.push_str(&format!("\t\t\tfor (uint256 j = 0; j < {}; j++) {{\n", POSEIDON_WIDTH));
+    s.push_str("\t\t\t\tuint256 m = internalLayer ? internalMatrix(i, j) : externalMds(i, j);\n");
+    s.push_str("\t\t\t\tacc = addmod(acc, mulmod(m, state[j], PRIME), PRIME);\n");
+    s.push_str("\t\t\t}\n");
+    s.push_str("\t\t\tnext[i] = acc;\n");
+    s.push_str("\t\t}\n");
+    s.push_str(&format!("\t\tfor (uint256 i = 0; i < {}; i++) {{\n", POSEIDON_WIDTH));
+    s.push_str("\t\t\tstate[i] = next[i];\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t}\n\n");
+
+    // The permutation
+    s.push_str("\t/// @dev The Poseidon2 permutation, applied in place to the state\n");
+    s.push_str(&format!(
+        "\tfunction permute(uint256[{w}] memory state) internal pure {{\n",
+        w = POSEIDON_WIDTH
+    ));
+    s.push_str("\t\t// Initial external linear layer\n");
+    s.push_str("\t\tapplyMatrix(state, false);\n\n");
+    s.push_str("\t\tuint256 half = FULL_ROUNDS / 2;\n");
+    s.push_str("\t\tuint256 extRound = 0;\n\n");
+    s.push_str("\t\t// First half of the full rounds\n");
+    s.push_str("\t\tfor (uint256 r = 0; r < half; r++) {\n");
+    s.push_str("\t\t\tfullRound(state, extRound++);\n");
+    s.push_str("\t\t}\n\n");
+    s.push_str("\t\t// Partial rounds\n");
+    s.push_str("\t\tfor (uint256 r = 0; r < PARTIAL_ROUNDS; r++) {\n");
+    s.push_str("\t\t\tstate[0] = addmod(state[0], internalRoundConstant(r), PRIME);\n");
+    s.push_str("\t\t\tstate[0] = sbox(state[0]);\n");
+    s.push_str("\t\t\tapplyMatrix(state, true);\n");
+    s.push_str("\t\t}\n\n");
+    s.push_str("\t\t// Second half of the full rounds\n");
+    s.push_str("\t\tfor (uint256 r = 0; r < half; r++) {\n");
+    s.push_str("\t\t\tfullRound(state, extRound++);\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t}\n\n");
+
+    // A single full round
+    s.push_str("\t/// @dev A single external (full) round\n");
+    s.push_str(&format!(
+        "\tfunction fullRound(uint256[{w}] memory state, uint256 round) internal pure {{\n",
+        w = POSEIDON_WIDTH
+    ));
+    s.push_str(&format!("\t\tfor (uint256 i = 0; i < {}; i++) {{\n", POSEIDON_WIDTH));
+    s.push_str("\t\t\tstate[i] = addmod(state[i], externalRoundConstant(round, i), PRIME);\n");
+    s.push_str("\t\t\tstate[i] = sbox(state[i]);\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\tapplyMatrix(state, false);\n");
+    s.push_str("\t}\n\n");
+
+    // The two-to-one compression matching `generate_zero_values`
+    s.push_str("\t/// @notice Two-to-one Poseidon2 compression, matching `renegade_crypto`\n");
+    s.push_str("\t/// @param left The left input\n");
+    s.push_str("\t/// @param right The right input\n");
+    s.push_str("\t/// @return The Poseidon hash of the two inputs\n");
+    s.push_str("\tfunction hash2(uint256 left, uint256 right) internal pure returns (uint256) {\n");
+    s.push_str(&format!("\t\tuint256[{w}] memory state;\n", w = POSEIDON_WIDTH));
+    s.push_str("\t\tstate[0] = left % PRIME;\n");
+    s.push_str("\t\tstate[1] = right % PRIME;\n");
+    s.push_str("\t\tstate[2] = 0;\n");
+    s.push_str("\t\tpermute(state);\n");
+    s.push_str("\t\treturn state[0];\n");
+    s.push_str("\t}\n\n");
+
+    // Embedded Rust-computed test vectors
+    s.push_str(&generate_poseidon_test_vectors());
+
+    s.push_str("}\n");
+    s
+}
+
+/// Emit a dense `WIDTH x WIDTH` matrix getter for the Poseidon2 linear layers
+fn generate_matrix_getter(name: &str, matrix: &[[Scalar; POSEIDON_WIDTH]; POSEIDON_WIDTH]) -> String {
+    let mut s = String::new();
+    s.push_str(&format!(
+        "\t/// @dev Entry `(i, j)` of the {} matrix\n",
+        name
+    ));
+    s.push_str(&format!(
+        "\tfunction {}(uint256 i, uint256 j) internal pure returns (uint256) {{\n",
+        name
+    ));
+    s.push_str(&format!(
+        "\t\tuint256[{w}][{w}] memory m = [\n",
+        w = POSEIDON_WIDTH
+    ));
+    for row in matrix.iter() {
+        s.push_str(&format!("\t\t\t[{}],\n", format_scalar_array(row.iter())));
+    }
+    s.push_str("\t\t];\n");
+    s.push_str("\t\treturn m[i][j];\n");
+    s.push_str("\t}\n\n");
+    s
+}
+
+/// Emit a `checkTestVectors` function asserting Rust-computed input/output pairs
+///
+/// The inputs are derived deterministically so the same vectors are produced on
+/// every run; the outputs come from `compute_poseidon_hash`, the exact function
+/// the rest of this tool uses to build the zero values.
+fn generate_poseidon_test_vectors() -> String {
+    let mut s = String::new();
+    s.push_str("\t/// @notice Assert the Solidity permutation matches the Rust reference vectors\n");
+    s.push_str("\t/// @dev Reverts if any embedded vector fails; call from a CI test\n");
+    s.push_str("\tfunction checkTestVectors() internal pure {\n");
+    for i in 0..POSEIDON_TEST_VECTORS {
+        let left = Scalar::from(i as u64);
+        let right = Scalar::from((i as u64) + 1);
+        let expected = compute_poseidon_hash(&[left, right]);
+        s.push_str(&format!(
+            "\t\trequire(hash2({}, {}) == {}, \"Poseidon2: test vector {} mismatch\");\n",
+            left, right, expected, i
+        ));
+    }
+    s.push_str("\t}\n");
+    s
+}
+
+/// Generate a Foundry test contract that runs the embedded Poseidon2 vectors
+///
+/// `checkTestVectors` is emitted into `Poseidon2.sol` but only reverts when
+/// invoked; this contract gives `forge test` a `test_` entry point that calls
+/// it, so a divergence between the generated Solidity permutation and the Rust
+/// hash fails CI instead of sitting dormant.
+fn generate_poseidon_foundry_test() -> String {
+    let mut s = String::new();
+    s.push_str("// SPDX-License-Identifier: MIT\n");
+    s.push_str("pragma solidity ^0.8.0;\n\n");
+    s.push_str("// ⚠ ️WARNING: This file is auto-generated by `codegen/merkle-zeros`. Do not edit directly.\n");
+    s.push_str(&format!(
+        "import {{{0}}} from \"./{0}.sol\";\n\n",
+        POSEIDON_CONTRACT_NAME
+    ));
+    s.push_str(&format!("contract {}Test {{\n", POSEIDON_CONTRACT_NAME));
+    s.push_str("\t/// @notice Execute the embedded Rust/Solidity parity vectors\n");
+    s.push_str("\tfunction test_PoseidonParityVectors() public pure {\n");
+    s.push_str(&format!("\t\t{}.checkTestVectors();\n", POSEIDON_CONTRACT_NAME));
+    s.push_str("\t}\n");
+    s.push_str("}\n");
+    s
+}
+
+/// The Poseidon2 S-box, `x^5` in the scalar field
+#[cfg(test)]
+fn poseidon_sbox(x: Scalar) -> Scalar {
+    let x2 = x * x;
+    let x4 = x2 * x2;
+    x4 * x
+}
+
+/// Multiply `state` by a dense `WIDTH x WIDTH` matrix in place
+#[cfg(test)]
+fn poseidon_apply_matrix(
+    matrix: &[[Scalar; POSEIDON_WIDTH]; POSEIDON_WIDTH],
+    state: &mut [Scalar; POSEIDON_WIDTH],
+) {
+    let mut next = [Scalar::zero(); POSEIDON_WIDTH];
+    for (i, row) in matrix.iter().enumerate() {
+        let mut acc = Scalar::zero();
+        for (j, m) in row.iter().enumerate() {
+            acc = acc + *m * state[j];
+        }
+        next[i] = acc;
+    }
+    *state = next;
+}
+
+/// Apply a single external (full) round in place
+#[cfg(test)]
+fn poseidon_full_round(
+    round_constants: &[Scalar; POSEIDON_WIDTH],
+    mds: &[[Scalar; POSEIDON_WIDTH]; POSEIDON_WIDTH],
+    state: &mut [Scalar; POSEIDON_WIDTH],
+) {
+    for (s, rc) in state.iter_mut().zip(round_constants.iter()) {
+        *s = poseidon_sbox(*s + *rc);
+    }
+    poseidon_apply_matrix(mds, state);
+}
+
+/// Reference implementation of the emitted Solidity two-to-one compression
+///
+/// This mirrors the generated `Poseidon2.hash2` exactly, sourcing its constants
+/// from the same [`Poseidon2Params`]. Comparing it against
+/// `compute_poseidon_hash` in the test below is what proves the on-chain absorb
+/// layout (`state = [left, right, 0]`, squeeze `state[0]`) matches the Rust
+/// construction the zero values are built from.
+#[cfg(test)]
+fn poseidon_hash2_reference(params: &Poseidon2Params, left: Scalar, right: Scalar) -> Scalar {
+    let external_rc = params.external_round_constants();
+    let internal_rc = params.internal_round_constants();
+    let external_mds = params.external_mds();
+    let internal_mat = params.internal_matrix();
+    let half = params.full_rounds() / 2;
+
+    let mut state = [left, right, Scalar::zero()];
+    poseidon_apply_matrix(&external_mds, &mut state);
+
+    let mut ext_round = 0;
+    for _ in 0..half {
+        poseidon_full_round(&external_rc[ext_round], &external_mds, &mut state);
+        ext_round += 1;
+    }
+    for rc in internal_rc.iter().take(params.partial_rounds()) {
+        state[0] = poseidon_sbox(state[0] + *rc);
+        poseidon_apply_matrix(&internal_mat, &mut state);
+    }
+    for _ in 0..half {
+        poseidon_full_round(&external_rc[ext_round], &external_mds, &mut state);
+        ext_round += 1;
+    }
+
+    state[0]
+}
+
 /// Entrypoint
 fn main() -> Result<()> {
     // Parse command line arguments
@@ -119,7 +577,7 @@ fn main() -> Result<()> {
     println!("Generating Merkle tree zero values");
 
     // Generate Solidity contract with Merkle tree zero values
-    let contract = generate_solidity_contract()?;
+    let contract = generate_solidity_contract(args.emit_poseidon)?;
 
     // Ensure output directory and file exist
     if !args.output_dir.exists() {
@@ -138,5 +596,82 @@ fn main() -> Result<()> {
         "Successfully generated Merkle zero values and wrote them to {}",
         output_file.display()
     );
+
+    // Optionally emit the companion Poseidon2 library so the generated constants
+    // can be recomputed on-chain with the same permutation used off-chain
+    if args.emit_poseidon {
+        let poseidon = generate_poseidon_contract();
+        let poseidon_file = args
+            .output_dir
+            .join(format!("{}.sol", POSEIDON_CONTRACT_NAME));
+        let mut file = File::create(&poseidon_file)
+            .map_err(|e| anyhow!("Failed to create output file: {}", e))?;
+        file.write_all(poseidon.as_bytes())
+            .map_err(|e| anyhow!("Failed to write to output file: {}", e))?;
+        println!(
+            "Successfully generated Poseidon2 gadget and wrote it to {}",
+            poseidon_file.display()
+        );
+
+        // Emit the Foundry test so `forge test` exercises the embedded vectors
+        let test = generate_poseidon_foundry_test();
+        let test_file = args
+            .output_dir
+            .join(format!("{}.t.sol", POSEIDON_CONTRACT_NAME));
+        let mut file = File::create(&test_file)
+            .map_err(|e| anyhow!("Failed to create output file: {}", e))?;
+        file.write_all(test.as_bytes())
+            .map_err(|e| anyhow!("Failed to write to output file: {}", e))?;
+        println!(
+            "Successfully generated Poseidon2 test and wrote it to {}",
+            test_file.display()
+        );
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Inserting the zero leaf into an empty tree at index 0 must reproduce the
+    /// root zero value when level `i` hashes against the leaf-relative zero
+    /// `zeros[i]`, the value the emitted `insert` reads as `getZeroValue(H-1-i)`.
+    /// The emitted Solidity and its root-first mapping are not exercised here.
+    #[test]
+    fn test_empty_tree_insert_yields_root_zero() {
+        let zeros = generate_zero_values();
+        let mut filled = zeros[..MERKLE_HEIGHT].to_vec();
+
+        let index: u64 = 0;
+        let mut cur = zeros[0];
+        for (i, filled_i) in filled.iter_mut().enumerate() {
+            if (index >> i) & 1 == 0 {
+                *filled_i = cur;
+                cur = compute_poseidon_hash(&[cur, zeros[i]]);
+            } else {
+                cur = compute_poseidon_hash(&[*filled_i, cur]);
+            }
+        }
+
+        assert_eq!(cur, zeros[MERKLE_HEIGHT]);
+    }
+
+    /// The same vectors embedded in `checkTestVectors` must agree with the Rust
+    /// reference hash, so a divergent Solidity absorb layout fails CI here.
+    #[test]
+    fn test_poseidon_solidity_parity() {
+        let params = Poseidon2Params::default();
+        for i in 0..POSEIDON_TEST_VECTORS as u64 {
+            let left = Scalar::from(i);
+            let right = Scalar::from(i + 1);
+            let expected = compute_poseidon_hash(&[left, right]);
+            let got = poseidon_hash2_reference(&params, left, right);
+            assert_eq!(
+                got, expected,
+                "Poseidon2 Solidity layout diverges from renegade_crypto at vector {i}"
+            );
+        }
+    }
+}
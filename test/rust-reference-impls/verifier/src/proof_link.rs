@@ -5,11 +5,23 @@
 //! elements in each circuit's witness.
 
 use super::*;
-use mpc_plonk::{errors::PlonkError, proof_system::PlonkKzgSnark, transcript::SolidityTranscript};
-use mpc_relation::{proof_linking::LinkableCircuit, traits::Circuit, Variable};
+use mpc_plonk::{
+    errors::PlonkError,
+    proof_system::{
+        structs::{CommitKey, LinkingHint, Proof, VerifyingKey},
+        PlonkKzgSnark,
+    },
+    transcript::SolidityTranscript,
+};
+use mpc_relation::{
+    proof_linking::{GroupLayout, LinkableCircuit},
+    traits::Circuit,
+    Variable,
+};
 use renegade_circuit_macros::circuit_type;
 use renegade_circuit_types::{traits::*, PlonkCircuit};
-use renegade_constants::{Scalar, ScalarField};
+use renegade_constants::{Scalar, ScalarField, SystemCurve};
+use std::collections::{HashMap, HashSet};
 
 pub(crate) const LINKING_WITNESS_SIZE: usize = 5;
 pub(crate) const LINKING_GROUP_NAME: &str = "sum-and-product-link-group";
@@ -178,3 +190,313 @@ pub fn generate_proofs(
         link_proof_converted,
     )
 }
+
+// --------------------------
+// | N-Circuit Proof Linking |
+// --------------------------
+
+/// An edge in a linking graph: the two circuits (by link-hint index) that share
+/// a named linking group
+///
+/// `first` and `second` index into the per-circuit link-hint slice, and `group`
+/// names the witness group the two circuits share. Representing links as edges
+/// lets a bundle join any pair of circuits through any named group.
+#[derive(Clone, Debug)]
+pub struct LinkGroupEdge {
+    /// Index into the link-hint slice of the first circuit in the pair
+    pub first: usize,
+    /// Index into the link-hint slice of the second circuit in the pair
+    pub second: usize,
+    /// Name of the linking group shared across the edge
+    pub group: String,
+}
+
+/// A declared linking graph over a bundle of circuits
+///
+/// Each edge names two circuits and the group whose witness slice they share.
+/// A single group may appear on multiple edges (a fan-out shared group) and a
+/// single pair of circuits may be joined by multiple disjoint groups.
+#[derive(Clone, Debug, Default)]
+pub struct LinkingGraph {
+    /// The edges of the graph
+    pub edges: Vec<LinkGroupEdge>,
+}
+
+/// Aggregate one linking proof per edge of a linking graph
+///
+/// `hints` holds one link hint per circuit (in the same order the circuits were
+/// proven), and `layouts` maps each group name to its canonical layout. The
+/// returned proofs are parallel to `graph.edges`.
+pub fn generate_linking_proofs(
+    hints: &[LinkingHint<SystemCurve>],
+    graph: &LinkingGraph,
+    layouts: &HashMap<String, GroupLayout>,
+    commit_key: &CommitKey<SystemCurve>,
+) -> Vec<LinkingProof> {
+    graph
+        .edges
+        .iter()
+        .map(|edge| {
+            let layout = layouts
+                .get(&edge.group)
+                .unwrap_or_else(|| panic!("no layout for linking group {}", edge.group));
+            let link_proof = PlonkKzgSnark::link_proofs::<SolidityTranscript>(
+                &hints[edge.first],
+                &hints[edge.second],
+                layout,
+                commit_key,
+            )
+            .unwrap();
+            LinkingProof::from(link_proof)
+        })
+        .collect()
+}
+
+/// Generate one [`ProofLinkingVK`] per distinct group in the linking graph
+///
+/// Groups are returned in the order they first appear along `graph.edges`.
+/// The emitted Solidity `verifyLinks` takes one verification key per edge, not
+/// per group.
+pub fn generate_linking_verification_keys(
+    graph: &LinkingGraph,
+    layouts: &HashMap<String, GroupLayout>,
+) -> Vec<(String, ProofLinkingVK)> {
+    let mut seen = HashSet::new();
+    let mut keys = Vec::new();
+    for edge in &graph.edges {
+        if seen.insert(edge.group.clone()) {
+            let layout = layouts
+                .get(&edge.group)
+                .unwrap_or_else(|| panic!("no layout for linking group {}", edge.group));
+            keys.push((edge.group.clone(), ProofLinkingVK::from(layout.clone())));
+        }
+    }
+    keys
+}
+
+/// Verify each edge of a linking graph
+///
+/// Each entry is `(proof_a_idx, proof_b_idx, group_vk, link_proof)`, referencing
+/// the proofs that the edge links. The edges are checked independently, so
+/// adding a new circuit to the bundle only adds edges — it never requires
+/// rewriting the verifier. On failure the index of the offending edge is
+/// returned so a single on-chain revert can pinpoint which link failed.
+pub fn verify_links(
+    edges: &[(usize, usize, ProofLinkingVK, LinkingProof)],
+    proofs: &[PlonkProof],
+) -> Result<(), usize> {
+    for (i, (a, b, group_vk, link_proof)) in edges.iter().enumerate() {
+        verify_link(&proofs[*a], &proofs[*b], group_vk, link_proof).map_err(|_| i)?;
+    }
+    Ok(())
+}
+
+/// Emit the Solidity verifier loop that mirrors [`verify_links`]
+///
+/// The generated function walks the declared edges and checks each one with the
+/// single-edge linking verifier, reverting on the first failure with the edge
+/// index. Adding a new circuit to the bundle only adds edges to the calldata —
+/// the emitted verifier does not need to be regenerated for a new topology.
+pub fn generate_verify_links_solidity() -> String {
+    let mut s = String::new();
+    s.push_str("// SPDX-License-Identifier: MIT\n");
+    s.push_str("pragma solidity ^0.8.0;\n\n");
+    s.push_str("// ⚠ ️WARNING: This file is auto-generated. Do not edit directly.\n");
+    s.push_str("import {Verifier} from \"./Verifier.sol\";\n");
+    s.push_str("import {ProofLinkingVK, LinkingProof, PlonkProof} from \"./Types.sol\";\n\n");
+    s.push_str("library LinkVerifier {\n");
+    s.push_str("\t/// @notice Verify every edge of a linking graph\n");
+    s.push_str("\t/// @param proofAIdx The first proof index of each edge\n");
+    s.push_str("\t/// @param proofBIdx The second proof index of each edge\n");
+    s.push_str("\t/// @param groupVks The per-edge linking verification key\n");
+    s.push_str("\t/// @param linkProofs The per-edge linking proof\n");
+    s.push_str("\t/// @param proofs The PlonK proofs referenced by the edges\n");
+    s.push_str("\tfunction verifyLinks(\n");
+    s.push_str("\t\tuint256[] calldata proofAIdx,\n");
+    s.push_str("\t\tuint256[] calldata proofBIdx,\n");
+    s.push_str("\t\tProofLinkingVK[] calldata groupVks,\n");
+    s.push_str("\t\tLinkingProof[] calldata linkProofs,\n");
+    s.push_str("\t\tPlonkProof[] calldata proofs\n");
+    s.push_str("\t) internal view {\n");
+    s.push_str("\t\tuint256 n = proofAIdx.length;\n");
+    s.push_str("\t\trequire(proofBIdx.length == n, \"LinkVerifier: length mismatch\");\n");
+    s.push_str("\t\trequire(groupVks.length == n, \"LinkVerifier: length mismatch\");\n");
+    s.push_str("\t\trequire(linkProofs.length == n, \"LinkVerifier: length mismatch\");\n\n");
+    s.push_str("\t\tfor (uint256 i = 0; i < n; i++) {\n");
+    s.push_str("\t\t\tbool ok = Verifier.verifyLink(\n");
+    s.push_str("\t\t\t\tproofs[proofAIdx[i]],\n");
+    s.push_str("\t\t\t\tproofs[proofBIdx[i]],\n");
+    s.push_str("\t\t\t\tgroupVks[i],\n");
+    s.push_str("\t\t\t\tlinkProofs[i]\n");
+    s.push_str("\t\t\t);\n");
+    s.push_str("\t\t\trequire(ok, string.concat(\"LinkVerifier: edge \", toString(i)));\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t}\n\n");
+    // Helper to render the failing edge index as readable decimal text.
+    s.push_str("\t/// @notice Render a uint256 as its decimal string\n");
+    s.push_str("\tfunction toString(uint256 value) internal pure returns (string memory) {\n");
+    s.push_str("\t\tif (value == 0) {\n");
+    s.push_str("\t\t\treturn \"0\";\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\tuint256 digits;\n");
+    s.push_str("\t\tfor (uint256 tmp = value; tmp != 0; tmp /= 10) {\n");
+    s.push_str("\t\t\tdigits++;\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\tbytes memory buffer = new bytes(digits);\n");
+    s.push_str("\t\tfor (uint256 tmp = value; tmp != 0; tmp /= 10) {\n");
+    s.push_str("\t\t\tbuffer[--digits] = bytes1(uint8(48 + tmp % 10));\n");
+    s.push_str("\t\t}\n");
+    s.push_str("\t\treturn string(buffer);\n");
+    s.push_str("\t}\n");
+    s.push_str("}\n");
+    s
+}
+
+// ----------------------
+// | Batch Verification |
+// ----------------------
+
+/// Batch-verify a set of independent PlonK proofs with a single multi-pairing
+///
+/// Each proof's KZG openings reduce to a pairing check of the form
+/// `e(A_i, [x]_2) == e(B_i, [1]_2)`. `batch_verify` defers to the KZG SNARK's
+/// batched verifier, which samples verifier-derived weights `r_i` via
+/// Fiat–Shamir over all proof transcripts, forms the random linear combinations
+/// `A = Σ r_i·A_i` and `B = Σ r_i·B_i`, and performs a single multi-pairing
+/// `e(A, [x]_2) == e(B, [1]_2)`. The combined check passes iff every individual
+/// check passes, except with negligible probability, so `N` proofs amortize to
+/// one pairing rather than `N`.
+///
+/// `vks`, `public_inputs`, and `proofs` are parallel slices with one entry per
+/// proof. Linking proofs are verified separately via [`verify_links`].
+///
+/// The on-chain `batchVerify` counterpart is intentionally not emitted here: a
+/// faithful Solidity verifier must reproduce `SolidityTranscript`'s squeeze
+/// byte-for-byte to derive the same weights, which is only possible alongside
+/// the verification-key codegen that owns the transcript layout. Emitting a
+/// standalone stub with an ad-hoc weight derivation would silently disagree
+/// with this function, so it is deferred to that codegen rather than faked.
+pub fn batch_verify(
+    vks: &[&VerifyingKey<SystemCurve>],
+    public_inputs: &[&[ScalarField]],
+    proofs: &[&Proof<SystemCurve>],
+) -> Result<(), PlonkError> {
+    let extra_init_msgs = vec![None; proofs.len()];
+    PlonkKzgSnark::<SystemCurve>::batch_verify::<SolidityTranscript>(
+        vks,
+        public_inputs,
+        proofs,
+        &extra_init_msgs,
+    )
+}
+
+// -----------------------
+// | Proptest Strategies |
+// -----------------------
+
+/// Randomized `proptest` generators for the linking circuits' witnesses
+///
+/// Gated behind the `test-dependencies` feature so the `proptest` dependency
+/// stays out of normal builds while downstream crates that share these link
+/// groups can still import the strategies to fuzz their own relations.
+#[cfg(feature = "test-dependencies")]
+pub mod proptest_strategies {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// A strategy producing an arbitrary field element
+    pub fn arb_scalar() -> impl Strategy<Value = Scalar> {
+        any::<[u8; 32]>().prop_map(|bytes| Scalar::from_be_bytes_mod_order(&bytes))
+    }
+
+    /// A strategy producing an arbitrary shared-witness array
+    pub fn arb_shared_witness() -> impl Strategy<Value = [Scalar; LINKING_WITNESS_SIZE]> {
+        proptest::array::uniform(arb_scalar())
+    }
+
+    prop_compose! {
+        /// A strategy producing an arbitrary [`SumCircuitWitness`]
+        pub fn arb_sum_witness()(
+            shared_witness in arb_shared_witness(),
+            private_witness in arb_scalar(),
+        ) -> SumCircuitWitness {
+            SumCircuitWitness { shared_witness, private_witness }
+        }
+    }
+
+    prop_compose! {
+        /// A strategy producing an arbitrary [`ProductCircuitWitness`]
+        pub fn arb_product_witness()(
+            shared_witness in arb_shared_witness(),
+            private_witness in arb_scalar(),
+        ) -> ProductCircuitWitness {
+            ProductCircuitWitness { shared_witness, private_witness }
+        }
+    }
+
+    /// A strategy producing a pair of witnesses that agree on their shared slice
+    ///
+    /// This is the honest case: both circuits are proven over the same
+    /// `shared_witness`, so the linking proof must verify.
+    pub fn arb_linked_witnesses(
+    ) -> impl Strategy<Value = (SumCircuitWitness, ProductCircuitWitness)> {
+        (arb_shared_witness(), arb_scalar(), arb_scalar()).prop_map(
+            |(shared, sum_private, product_private)| {
+                (
+                    SumCircuitWitness { shared_witness: shared, private_witness: sum_private },
+                    ProductCircuitWitness { shared_witness: shared, private_witness: product_private },
+                )
+            },
+        )
+    }
+}
+
+#[cfg(all(test, feature = "test-dependencies"))]
+mod test {
+    use super::proptest_strategies::*;
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        /// Honest proofs over a shared witness always verify, including the link
+        #[test]
+        fn test_honest_proofs_verify((sum_witness, product_witness) in arb_linked_witnesses()) {
+            let sum_statement = sum_witness.statement();
+            let product_statement = product_witness.statement();
+            let (sum_proof, product_proof, link_proof) =
+                generate_proofs(sum_witness, product_witness);
+
+            let sum_vk = generate_sum_circuit_verification_key();
+            let product_vk = generate_product_circuit_verification_key();
+            let group_vk = generate_sum_product_linking_verification_key();
+
+            prop_assert!(verify(&sum_vk, sum_statement, &sum_proof).is_ok());
+            prop_assert!(verify(&product_vk, product_statement, &product_proof).is_ok());
+
+            let proofs = [sum_proof, product_proof];
+            let edges = [(0, 1, group_vk, link_proof)];
+            prop_assert!(verify_links(&edges, &proofs).is_ok());
+        }
+
+        /// Disagreeing shared witnesses make the linking proof fail, even though
+        /// each circuit is proven against the statement derived from its own
+        /// witness; only the link verification is checked here.
+        #[test]
+        fn test_mismatched_shared_witness_fails(
+            (sum_witness, mut product_witness) in arb_linked_witnesses(),
+            index in 0..LINKING_WITNESS_SIZE,
+        ) {
+            // Perturb a single shared element in only the product circuit so the
+            // two shared_witness arrays disagree.
+            product_witness.shared_witness[index] += Scalar::one();
+
+            let (sum_proof, product_proof, link_proof) =
+                generate_proofs(sum_witness, product_witness);
+            let group_vk = generate_sum_product_linking_verification_key();
+
+            let proofs = [sum_proof, product_proof];
+            let edges = [(0, 1, group_vk, link_proof)];
+            prop_assert!(verify_links(&edges, &proofs).is_err());
+        }
+    }
+}